@@ -0,0 +1,119 @@
+use super::Forge;
+use crate::models::{Branch, FullCommit, PullRequest, Repo, User};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, ETAG, IF_NONE_MATCH, USER_AGENT};
+use std::sync::Arc;
+
+/// Targets a self-hosted Forgejo or Gitea instance, whose `/api/v1` surface is
+/// close enough to GitHub's that the same `models` types deserialize unchanged.
+#[derive(Clone)]
+pub struct ForgejoForge {
+    client: Arc<reqwest::Client>,
+    base_url: String,
+}
+
+impl ForgejoForge {
+    pub fn new(base_url: String, token: String) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("github-commit-notifier"));
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("token {}", token))?);
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+        Ok(Self {
+            client: Arc::new(client),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn get_paged<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<T>, Option<String>)> {
+        let mut request = self.client.get(url);
+        if let Some(etag) = etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok((Vec::new(), etag.map(String::from)));
+        }
+
+        let new_etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+
+        if response.status().is_success() {
+            let items = response.json().await?;
+            Ok((items, new_etag))
+        } else {
+            Err(anyhow!("Failed to fetch {}: {}", url, response.status()))
+        }
+    }
+}
+
+#[async_trait]
+impl Forge for ForgejoForge {
+    async fn list_repos(&self, org: &str) -> Result<(Vec<Repo>, Option<String>)> {
+        let url = format!("{}/api/v1/orgs/{}/repos", self.base_url, org);
+        self.get_paged(&url, None).await
+    }
+
+    async fn list_branches(
+        &self,
+        repo_full_name: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<Branch>, Option<String>)> {
+        let url = format!("{}/api/v1/repos/{}/branches", self.base_url, repo_full_name);
+        self.get_paged(&url, etag).await
+    }
+
+    async fn get_commit(&self, repo_full_name: &str, sha: &str) -> Result<FullCommit> {
+        let url = format!(
+            "{}/api/v1/repos/{}/commits/{}",
+            self.base_url, repo_full_name, sha
+        );
+        let response = self.client.get(&url).send().await?;
+        if response.status().is_success() {
+            let commit = response.json().await?;
+            Ok(commit)
+        } else {
+            Err(anyhow!(
+                "Failed to fetch commit {}: {}",
+                sha,
+                response.status()
+            ))
+        }
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo_full_name: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<PullRequest>, Option<String>)> {
+        let url = format!("{}/api/v1/repos/{}/pulls", self.base_url, repo_full_name);
+        self.get_paged(&url, etag).await
+    }
+
+    async fn get_user(&self, login: &str) -> Result<User> {
+        let url = format!("{}/api/v1/users/{}", self.base_url, login);
+        let response = self.client.get(&url).send().await?;
+        if response.status().is_success() {
+            let user = response.json().await?;
+            Ok(user)
+        } else {
+            Err(anyhow!("Failed to fetch user {}: {}", login, response.status()))
+        }
+    }
+
+    fn branch_url(&self, repo_full_name: &str, branch_name: &str) -> String {
+        format!("{}/{}/src/branch/{}", self.base_url, repo_full_name, branch_name)
+    }
+}
@@ -1,14 +1,16 @@
-use crate::models::{FullCommit, User};
+use super::Forge;
+use crate::models::{Branch, FullCommit, PullRequest, Repo, User};
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, ETAG, IF_NONE_MATCH, USER_AGENT};
 use std::sync::Arc;
 
 #[derive(Clone)]
-pub struct GithubClient {
+pub struct GithubForge {
     client: Arc<reqwest::Client>,
 }
 
-impl GithubClient {
+impl GithubForge {
     pub fn new(token: String) -> Result<Self> {
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, HeaderValue::from_static("github-commit-notifier"));
@@ -21,7 +23,7 @@ impl GithubClient {
         Ok(Self { client: Arc::new(client) })
     }
 
-    pub async fn get_paged<T: serde::de::DeserializeOwned>(
+    async fn get_paged<T: serde::de::DeserializeOwned>(
         &self,
         url: &str,
         etag: Option<&str>,
@@ -50,8 +52,25 @@ impl GithubClient {
             Err(anyhow!("Failed to fetch {}: {}", url, response.status()))
         }
     }
+}
+
+#[async_trait]
+impl Forge for GithubForge {
+    async fn list_repos(&self, org: &str) -> Result<(Vec<Repo>, Option<String>)> {
+        let url = format!("https://api.github.com/orgs/{}/repos", org);
+        self.get_paged(&url, None).await
+    }
 
-    pub async fn get_commit(&self, repo_full_name: &str, sha: &str) -> Result<FullCommit> {
+    async fn list_branches(
+        &self,
+        repo_full_name: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<Branch>, Option<String>)> {
+        let url = format!("https://api.github.com/repos/{}/branches", repo_full_name);
+        self.get_paged(&url, etag).await
+    }
+
+    async fn get_commit(&self, repo_full_name: &str, sha: &str) -> Result<FullCommit> {
         let url = format!(
             "https://api.github.com/repos/{}/commits/{}",
             repo_full_name, sha
@@ -69,18 +88,27 @@ impl GithubClient {
         }
     }
 
-    pub async fn get_user(&self, username: &str) -> Result<User> {
-        let url = format!("https://api.github.com/users/{}", username);
+    async fn list_pull_requests(
+        &self,
+        repo_full_name: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<PullRequest>, Option<String>)> {
+        let url = format!("https://api.github.com/repos/{}/pulls", repo_full_name);
+        self.get_paged(&url, etag).await
+    }
+
+    async fn get_user(&self, login: &str) -> Result<User> {
+        let url = format!("https://api.github.com/users/{}", login);
         let response = self.client.get(&url).send().await?;
         if response.status().is_success() {
             let user = response.json().await?;
             Ok(user)
         } else {
-            Err(anyhow!(
-                "Failed to fetch user {}: {}",
-                username,
-                response.status()
-            ))
+            Err(anyhow!("Failed to fetch user {}: {}", login, response.status()))
         }
     }
-}
\ No newline at end of file
+
+    fn branch_url(&self, repo_full_name: &str, branch_name: &str) -> String {
+        format!("https://github.com/{}/tree/{}", repo_full_name, branch_name)
+    }
+}
@@ -0,0 +1,60 @@
+mod github;
+pub use github::GithubForge;
+
+#[cfg(feature = "forgejo")]
+mod forgejo;
+#[cfg(feature = "forgejo")]
+pub use forgejo::ForgejoForge;
+
+use crate::models::{Branch, FullCommit, PullRequest, Repo, User};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// A code-hosting backend `GithubNotifier` polls against. Implementations own
+/// their own base URL and API shape; callers only deal in `models` types and
+/// opaque ETags.
+#[async_trait]
+pub trait Forge: Send + Sync {
+    async fn list_repos(&self, org: &str) -> Result<(Vec<Repo>, Option<String>)>;
+
+    async fn list_branches(
+        &self,
+        repo_full_name: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<Branch>, Option<String>)>;
+
+    async fn get_commit(&self, repo_full_name: &str, sha: &str) -> Result<FullCommit>;
+
+    async fn list_pull_requests(
+        &self,
+        repo_full_name: &str,
+        etag: Option<&str>,
+    ) -> Result<(Vec<PullRequest>, Option<String>)>;
+
+    async fn get_user(&self, login: &str) -> Result<User>;
+
+    /// Web URL for a branch, for notifications. Unlike commits and PRs, the
+    /// forge APIs don't return an `html_url` for branches, so each backend
+    /// builds its own.
+    fn branch_url(&self, repo_full_name: &str, branch_name: &str) -> String;
+}
+
+/// Select a `Forge` implementation from `FORGE` (default `github`). Self-hosted
+/// Forgejo/Gitea users set `FORGE=forgejo` and `FORGE_BASE_URL` (requires this
+/// crate's `forgejo` feature, on by default).
+pub fn from_env(token: String) -> Result<Box<dyn Forge>> {
+    let kind = std::env::var("FORGE").unwrap_or_else(|_| "github".to_string());
+    match kind.as_str() {
+        "github" => Ok(Box::new(GithubForge::new(token)?)),
+        #[cfg(feature = "forgejo")]
+        "forgejo" => {
+            let base_url = std::env::var("FORGE_BASE_URL")
+                .map_err(|_| anyhow::anyhow!("FORGE_BASE_URL not set"))?;
+            Ok(Box::new(ForgejoForge::new(base_url, token)?))
+        }
+        other => Err(anyhow::anyhow!(
+            "Unsupported or disabled forge '{}' (is the matching cargo feature enabled?)",
+            other
+        )),
+    }
+}
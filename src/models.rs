@@ -20,6 +20,10 @@ pub struct Commit {
 pub struct FullCommit {
     pub html_url: String,
     pub commit: CommitDetails,
+    #[serde(default)]
+    pub stats: Option<CommitStats>,
+    #[serde(default)]
+    pub files: Vec<CommitFile>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -33,6 +37,19 @@ pub struct CommitAuthor {
     pub name: String,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct CommitStats {
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CommitFile {
+    pub filename: String,
+    #[serde(default)]
+    pub patch: Option<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct PullRequest {
     pub id: u64,
@@ -46,3 +63,37 @@ pub struct User {
     pub login: String,
     pub name: Option<String>,
 }
+
+// --- Webhook event payloads (https://docs.github.com/webhooks/webhook-events-and-payloads) ---
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WebhookRepo {
+    pub full_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WebhookCommitAuthor {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WebhookCommit {
+    pub message: String,
+    pub url: String,
+    pub author: WebhookCommitAuthor,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PushEvent {
+    pub repository: WebhookRepo,
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub head_commit: Option<WebhookCommit>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PullRequestEvent {
+    pub action: String,
+    pub repository: WebhookRepo,
+    pub pull_request: PullRequest,
+}
@@ -1,18 +1,24 @@
-use crate::github_client::GithubClient;
-use crate::models::{Branch, FullCommit, PullRequest, Repo};
+use crate::forge::Forge;
+use crate::models::{FullCommit, Repo};
+use crate::notifiers::{self, EventKind, Notification, Notifier};
+use crate::store::Store;
 use anyhow::{Context, Result};
 use futures::{stream, StreamExt};
 use std::collections::{HashMap, HashSet};
-use std::process::Command;
+use std::env;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 const CONCURRENT_REQUESTS: usize = 10;
+const DEFAULT_ALERT_DEDUPE_WINDOW_SECS: u64 = 300;
 
 #[derive(Clone)]
 pub struct GithubNotifier {
-    client: Arc<GithubClient>,
+    forge: Arc<Box<dyn Forge>>,
     orgs: Vec<String>,
+    sinks: Arc<Vec<Box<dyn Notifier>>>,
+    store: Arc<Store>,
+    alert_dedupe_window_secs: u64,
     seen_commits: Arc<Mutex<HashMap<String, String>>>,
     seen_prs: Arc<Mutex<HashMap<String, HashSet<u64>>>>,
     seen_branches: Arc<Mutex<HashMap<String, HashSet<String>>>>,
@@ -20,24 +26,44 @@ pub struct GithubNotifier {
 }
 
 impl GithubNotifier {
-    pub fn new(token: String, orgs: String) -> Result<Self> {
+    pub fn new(forge: Box<dyn Forge>, orgs: String, db_path: &str) -> Result<Self> {
+        let store = Store::open(db_path)?;
+        let seen_commits = store
+            .load_seen_commits()
+            .context("Failed to load seen commits from database")?;
+        let seen_prs = store
+            .load_seen_prs()
+            .context("Failed to load seen PRs from database")?;
+        let seen_branches = store
+            .load_seen_branches()
+            .context("Failed to load seen branches from database")?;
+        let etags = store
+            .load_etags()
+            .context("Failed to load ETags from database")?;
+        let alert_dedupe_window_secs = env::var("ALERT_DEDUPE_WINDOW_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_ALERT_DEDUPE_WINDOW_SECS);
+
         Ok(Self {
-            client: Arc::new(GithubClient::new(token)?),
+            forge: Arc::new(forge),
             orgs: orgs.split(',').map(String::from).collect(),
-            seen_commits: Arc::new(Mutex::new(HashMap::new())),
-            seen_prs: Arc::new(Mutex::new(HashMap::new())),
-            seen_branches: Arc::new(Mutex::new(HashMap::new())),
-            etags: Arc::new(Mutex::new(HashMap::new())),
+            sinks: Arc::new(notifiers::sinks_from_env()),
+            store: Arc::new(store),
+            alert_dedupe_window_secs,
+            seen_commits: Arc::new(Mutex::new(seen_commits)),
+            seen_prs: Arc::new(Mutex::new(seen_prs)),
+            seen_branches: Arc::new(Mutex::new(seen_branches)),
+            etags: Arc::new(Mutex::new(etags)),
         })
     }
 
     pub async fn check_all_repos(&self) -> Result<()> {
         let mut all_repos = Vec::new();
         for org in &self.orgs {
-            let url = format!("https://api.github.com/orgs/{}/repos", org);
             let (repos, _etag) = self
-                .client
-                .get_paged::<Repo>(&url, None)
+                .forge
+                .list_repos(org)
                 .await
                 .with_context(|| format!("Failed to get repos for org {}", org))?;
             all_repos.extend(repos);
@@ -65,15 +91,13 @@ impl GithubNotifier {
     }
 
     async fn check_branches_and_commits(&self, repo: &Repo) -> Result<()> {
-        let url = format!("https://api.github.com/repos/{}/branches", repo.full_name);
-        let etag = self.etags.lock().await.get(&url).cloned();
-        let (branches, new_etag) = self
-            .client
-            .get_paged::<Branch>(&url, etag.as_deref())
-            .await?;
+        let etag_key = format!("branches:{}", repo.full_name);
+        let etag = self.etags.lock().await.get(&etag_key).cloned();
+        let (branches, new_etag) = self.forge.list_branches(&repo.full_name, etag.as_deref()).await?;
 
         if let Some(new_etag) = new_etag {
-            self.etags.lock().await.insert(url.clone(), new_etag);
+            self.put_etag(&etag_key, &new_etag).await;
+            self.etags.lock().await.insert(etag_key.clone(), new_etag);
         }
 
         let mut current_branches = HashSet::new();
@@ -84,17 +108,31 @@ impl GithubNotifier {
             current_branches.insert(branch.name.clone());
             branches_with_commit_info.push(branch.clone());
             let key = format!("{}/{}", repo.full_name, branch.name);
-            let mut seen_commits = self.seen_commits.lock().await;
-            if let Some(seen_sha) = seen_commits.get(&key) {
-                if *seen_sha != branch.commit.sha {
+            // Hold the lock only long enough to snapshot the seen sha, then drop it
+            // before awaiting get_commit/put_seen_commit below, so other concurrently
+            // polled repos' commit checks aren't serialized behind this one's network
+            // fetch or DB write (seen_commits is shared across all repos).
+            let seen_sha = self.seen_commits.lock().await.get(&key).cloned();
+            match seen_sha {
+                Some(seen_sha) if seen_sha != branch.commit.sha => {
+                    // Don't mark the sha as seen here: the commits_to_notify loop
+                    // below only inserts it into seen_commits/the store once
+                    // notify_commit has actually delivered the alert, so a transient
+                    // fetch *or* delivery failure leaves it unseen and gets retried
+                    // next cycle instead of being silently dropped.
                     match self
-                        .client
+                        .forge
                         .get_commit(&repo.full_name, &branch.commit.sha)
                         .await
                     {
                         Ok(commit_details) => {
-                            commits_to_notify
-                                .push((repo.full_name.clone(), branch.name.clone(), commit_details));
+                            commits_to_notify.push((
+                                key,
+                                branch.commit.sha.clone(),
+                                repo.full_name.clone(),
+                                branch.name.clone(),
+                                commit_details,
+                            ));
                         }
                         Err(e) => {
                             eprintln!(
@@ -103,34 +141,58 @@ impl GithubNotifier {
                             );
                         }
                     }
-                    seen_commits.insert(key, branch.commit.sha);
                 }
-            } else {
-                seen_commits.insert(key, branch.commit.sha);
+                Some(_) => {}
+                None => {
+                    self.put_seen_commit(&key, &branch.commit.sha).await;
+                    self.seen_commits.lock().await.insert(key, branch.commit.sha);
+                }
             }
         }
 
-        let new_branches_to_notify = {
-            let mut seen_branches = self.seen_branches.lock().await;
-            if let Some(seen_repo_branches) = seen_branches.get_mut(&repo.full_name) {
+        // Decide what's new and what needs persisting with the lock held only long
+        // enough to read/compare the in-memory sets, then drop it before awaiting
+        // the (slow) store writes below so other repos' checks aren't serialized
+        // behind this one's DB round-trips.
+        let (new_branches_to_notify, branches_to_persist) = {
+            let seen_branches = self.seen_branches.lock().await;
+            if let Some(seen_repo_branches) = seen_branches.get(&repo.full_name) {
                 let new_branches = current_branches
                     .difference(seen_repo_branches)
                     .cloned()
                     .collect::<Vec<_>>();
-                for branch_name in &new_branches {
-                    seen_repo_branches.insert(branch_name.clone());
-                }
-                new_branches
+                (new_branches.clone(), new_branches)
             } else {
-                seen_branches
-                    .insert(repo.full_name.clone(), current_branches);
-                Vec::new()
+                (Vec::new(), current_branches.iter().cloned().collect::<Vec<_>>())
             }
         };
 
-        for (repo_full_name, branch_name, commit) in commits_to_notify {
-            self.notify_commit(&repo_full_name, &branch_name, &commit)
+        for branch_name in &branches_to_persist {
+            self.put_seen_branch(&repo.full_name, branch_name).await;
+        }
+        self.seen_branches
+            .lock()
+            .await
+            .entry(repo.full_name.clone())
+            .or_default()
+            .extend(branches_to_persist);
+
+        for (key, sha, repo_full_name, branch_name, commit) in commits_to_notify {
+            let diff_summary = format_diff_summary(&commit);
+            let delivered = self
+                .notify_commit(
+                    &repo_full_name,
+                    &branch_name,
+                    &commit.commit.author.name,
+                    &commit.commit.message,
+                    &commit.html_url,
+                    &diff_summary,
+                )
                 .await;
+            if delivered {
+                self.put_seen_commit(&key, &sha).await;
+                self.seen_commits.lock().await.insert(key, sha);
+            }
         }
 
         for branch_name in new_branches_to_notify {
@@ -139,13 +201,17 @@ impl GithubNotifier {
                 .find(|b| b.name == branch_name)
             {
                 match self
-                    .client
+                    .forge
                     .get_commit(&repo.full_name, &branch_info.commit.sha)
                     .await
                 {
                     Ok(commit_details) => {
-                        self.notify_branch(&repo.full_name, &branch_name, &commit_details)
-                            .await;
+                        self.notify_branch(
+                            &repo.full_name,
+                            &branch_name,
+                            &commit_details.commit.author.name,
+                        )
+                        .await;
                     }
                     Err(e) => {
                         eprintln!(
@@ -161,24 +227,42 @@ impl GithubNotifier {
     }
 
     async fn check_pull_requests(&self, repo: &Repo) -> Result<()> {
-        let url = format!("https://api.github.com/repos/{}/pulls", repo.full_name);
-        let etag = self.etags.lock().await.get(&url).cloned();
-        let (prs, new_etag) = self
-            .client
-            .get_paged::<PullRequest>(&url, etag.as_deref())
-            .await?;
+        let etag_key = format!("pulls:{}", repo.full_name);
+        let etag = self.etags.lock().await.get(&etag_key).cloned();
+        let (prs, new_etag) = self.forge.list_pull_requests(&repo.full_name, etag.as_deref()).await?;
 
         if let Some(new_etag) = new_etag {
-            self.etags.lock().await.insert(url, new_etag);
+            self.put_etag(&etag_key, &new_etag).await;
+            self.etags.lock().await.insert(etag_key, new_etag);
         }
 
+        // As with branches above, only hold the lock long enough to read the
+        // in-memory baseline; the awaited store writes and (for new PRs) the
+        // get_user lookups happen after it's dropped so they don't serialize other
+        // repos' concurrent checks behind this one.
+        let is_new_repo = !self.seen_prs.lock().await.contains_key(&repo.full_name);
+
         let mut new_prs_to_notify = Vec::new();
-        {
-            let mut seen_repo_prs = self.seen_prs.lock().await;
-            let seen_repo_prs = seen_repo_prs.entry(repo.full_name.clone()).or_default();
+        if is_new_repo {
+            // First time we've seen this repo at all: record the currently open PRs
+            // as a baseline without notifying, so a fresh DB doesn't flood every
+            // already-open PR (mirrors the branches baseline above).
+            for pr in &prs {
+                self.put_seen_pr(&repo.full_name, pr.id).await;
+            }
+            let baseline = prs.iter().map(|pr| pr.id).collect();
+            self.seen_prs.lock().await.insert(repo.full_name.clone(), baseline);
+        } else {
+            let seen_repo_prs = self
+                .seen_prs
+                .lock()
+                .await
+                .get(&repo.full_name)
+                .cloned()
+                .unwrap_or_default();
             for pr in prs {
                 if !seen_repo_prs.contains(&pr.id) {
-                    match self.client.get_user(&pr.user.login).await {
+                    match self.forge.get_user(&pr.user.login).await {
                         Ok(user) => {
                             let mut pr_with_full_user = pr.clone();
                             pr_with_full_user.user = user;
@@ -189,52 +273,363 @@ impl GithubNotifier {
                             new_prs_to_notify.push(pr.clone());
                         }
                     }
-                    seen_repo_prs.insert(pr.id);
                 }
             }
         }
 
+        // Only insert the PR id into seen_prs/the store once notify_pr has actually
+        // delivered the alert, so a delivery failure is retried next cycle instead
+        // of being silently dropped.
         for pr in new_prs_to_notify {
-            self.notify_pr(&repo.full_name, &pr).await;
+            let author_name = pr.user.name.as_deref().unwrap_or(&pr.user.login);
+            let delivered = self
+                .notify_pr(&repo.full_name, pr.id, &pr.title, author_name, &pr.html_url)
+                .await;
+            if delivered {
+                self.put_seen_pr(&repo.full_name, pr.id).await;
+                self.seen_prs
+                    .lock()
+                    .await
+                    .entry(repo.full_name.clone())
+                    .or_default()
+                    .insert(pr.id);
+            }
         }
         Ok(())
     }
 
-    async fn notify_commit(&self, repo_full_name: &str, branch_name: &str, commit: &FullCommit) {
+    /// Record a new commit notification. Shared by the polling path (which resolves
+    /// the commit details from the REST API) and the webhook receiver (which reads
+    /// them straight off the `push` event payload). Returns whether the alert was
+    /// actually delivered (or had already been delivered within the dedupe window),
+    /// so callers can gate seen-state writes on real delivery rather than on fetch
+    /// success.
+    pub async fn notify_commit(
+        &self,
+        repo_full_name: &str,
+        branch_name: &str,
+        author: &str,
+        message: &str,
+        url: &str,
+        diff_summary: &str,
+    ) -> bool {
+        let hash = alert_hash(&["commit", repo_full_name, branch_name, message, url]);
+        if self.already_alerted(&hash).await {
+            return true;
+        }
+
         let title = format!("New Commit on {}/{}", repo_full_name, branch_name);
-        let body = format!(
-            "By {}: {}\nURL: {}",
-            commit.commit.author.name, commit.commit.message, commit.html_url
-        );
+        let body = if diff_summary.is_empty() {
+            format!("By {}: {}\nURL: {}", author, message, url)
+        } else {
+            format!("By {}: {}\n{}\nURL: {}", author, message, diff_summary, url)
+        };
         println!("{} - {}", title, body);
-        self.send_notification(&title, &body);
+        let delivered = self
+            .send_notification(Notification {
+                kind: EventKind::Commit,
+                repo: repo_full_name,
+                url,
+                author,
+                message,
+                title: &title,
+                body: &body,
+            })
+            .await;
+        if delivered {
+            self.record_alert(&hash).await;
+        }
+        delivered
     }
 
-    async fn notify_pr(&self, repo_full_name: &str, pr: &PullRequest) {
+    pub async fn notify_pr(
+        &self,
+        repo_full_name: &str,
+        id: u64,
+        title_text: &str,
+        author: &str,
+        url: &str,
+    ) -> bool {
+        let hash = alert_hash(&["pull_request", repo_full_name, &id.to_string()]);
+        if self.already_alerted(&hash).await {
+            return true;
+        }
+
         let title = format!("New PR in {}", repo_full_name);
-        let author_name = pr.user.name.as_deref().unwrap_or(&pr.user.login);
-        let body = format!(
-            "#{} {}\nBy: {}\nURL: {}",
-            pr.id, pr.title, author_name, pr.html_url
-        );
+        let body = format!("#{} {}\nBy: {}\nURL: {}", id, title_text, author, url);
         println!("{} - {}", title, body);
-        self.send_notification(&title, &body);
+        let delivered = self
+            .send_notification(Notification {
+                kind: EventKind::PullRequest,
+                repo: repo_full_name,
+                url,
+                author,
+                message: title_text,
+                title: &title,
+                body: &body,
+            })
+            .await;
+        if delivered {
+            self.record_alert(&hash).await;
+        }
+        delivered
     }
 
-    async fn notify_branch(&self, repo_full_name: &str, branch_name: &str, commit: &FullCommit) {
+    pub async fn notify_branch(&self, repo_full_name: &str, branch_name: &str, author: &str) -> bool {
+        let hash = alert_hash(&["branch", repo_full_name, branch_name]);
+        if self.already_alerted(&hash).await {
+            return true;
+        }
+
         let title = format!("New Branch in {}", repo_full_name);
-        let branch_url = format!("https://github.com/{}/tree/{}", repo_full_name, branch_name);
-        let body = format!(
-            "Branch: {}\nBy: {}\nURL: {}",
-            branch_name, commit.commit.author.name, branch_url
-        );
+        let branch_url = self.forge.branch_url(repo_full_name, branch_name);
+        let body = format!("Branch: {}\nBy: {}\nURL: {}", branch_name, author, branch_url);
         println!("{} - {}", title, body);
-        self.send_notification(&title, &body);
+        let delivered = self
+            .send_notification(Notification {
+                kind: EventKind::Branch,
+                repo: repo_full_name,
+                url: &branch_url,
+                author,
+                message: branch_name,
+                title: &title,
+                body: &body,
+            })
+            .await;
+        if delivered {
+            self.record_alert(&hash).await;
+        }
+        delivered
     }
 
-    fn send_notification(&self, title: &str, body: &str) {
-        if let Err(e) = Command::new("notify-send").arg(title).arg(body).spawn() {
-            eprintln!("Failed to send notification: {}", e);
+    /// Fans the notification out to every configured sink, returning whether at
+    /// least one sink actually accepted it. A sink failing doesn't stop the others
+    /// from being tried, but if every sink fails the caller must treat the alert as
+    /// undelivered rather than recording it as sent.
+    async fn send_notification(&self, notification: Notification<'_>) -> bool {
+        let mut delivered = false;
+        for sink in self.sinks.iter() {
+            match sink.send(&notification).await {
+                Ok(()) => delivered = true,
+                Err(e) => eprintln!("Notification sink failed: {}", e),
+            }
+        }
+        delivered
+    }
+
+    /// Consults the alert ledger so a branch head bouncing back and forth, or a PR
+    /// re-appearing across polls, doesn't re-alert within the dedupe window.
+    async fn already_alerted(&self, hash: &str) -> bool {
+        let now = unix_now();
+        let dedupe_window = self.alert_dedupe_window_secs;
+        let hash_owned = hash.to_string();
+        match self
+            .with_store(move |store| store.was_recently_alerted(&hash_owned, dedupe_window, now))
+            .await
+        {
+            Ok(recent) => recent,
+            Err(e) => {
+                eprintln!("Failed to check alert ledger for {}: {}", hash, e);
+                false
+            }
         }
     }
+
+    async fn record_alert(&self, hash: &str) {
+        let hash_owned = hash.to_string();
+        if let Err(e) = self
+            .with_store(move |store| store.record_alert(&hash_owned, unix_now()))
+            .await
+        {
+            eprintln!("Failed to record alert {} in ledger: {}", hash, e);
+        }
+    }
+
+    async fn put_etag(&self, url: &str, etag: &str) {
+        let (url, etag) = (url.to_string(), etag.to_string());
+        if let Err(e) = self
+            .with_store(move |store| store.put_etag(&url, &etag))
+            .await
+        {
+            eprintln!("Failed to persist ETag: {}", e);
+        }
+    }
+
+    async fn put_seen_commit(&self, repo_branch: &str, sha: &str) {
+        let (repo_branch, sha) = (repo_branch.to_string(), sha.to_string());
+        if let Err(e) = self
+            .with_store(move |store| store.put_seen_commit(&repo_branch, &sha))
+            .await
+        {
+            eprintln!("Failed to persist seen commit: {}", e);
+        }
+    }
+
+    async fn put_seen_branch(&self, repo: &str, name: &str) {
+        let (repo, name) = (repo.to_string(), name.to_string());
+        if let Err(e) = self
+            .with_store(move |store| store.put_seen_branch(&repo, &name))
+            .await
+        {
+            eprintln!("Failed to persist seen branch: {}", e);
+        }
+    }
+
+    async fn put_seen_pr(&self, repo: &str, pr_id: u64) {
+        let repo = repo.to_string();
+        if let Err(e) = self
+            .with_store(move |store| store.put_seen_pr(&repo, pr_id))
+            .await
+        {
+            eprintln!("Failed to persist seen PR {}: {}", pr_id, e);
+        }
+    }
+
+    /// Runs a blocking rusqlite call on the blocking thread pool instead of an
+    /// async worker thread. Up to `CONCURRENT_REQUESTS` repo checks poll
+    /// concurrently and all share this one `Store`/connection, so calling rusqlite
+    /// directly from async code would stall the runtime under load.
+    async fn with_store<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Store) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || f(&store))
+            .await
+            .context("database task panicked")?
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A stable hex digest identifying a notification, used as the alert ledger key.
+fn alert_hash(parts: &[&str]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(parts.join("\u{1f}").as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+const DEFAULT_DIFF_BYTE_BUDGET: usize = 4000;
+
+/// Renders a `N files changed, +X/-Y` line, and, when `NOTIFY_INCLUDE_DIFF` is set,
+/// the unified diff hunks truncated to `NOTIFY_DIFF_BYTE_BUDGET` bytes (default
+/// 4000) so a large commit doesn't blow up a desktop popup or email.
+fn format_diff_summary(commit: &FullCommit) -> String {
+    let Some(stats) = &commit.stats else {
+        return String::new();
+    };
+
+    let mut summary = format!(
+        "{} file{} changed, +{}/-{}",
+        commit.files.len(),
+        if commit.files.len() == 1 { "" } else { "s" },
+        stats.additions,
+        stats.deletions
+    );
+
+    let include_diff = env::var("NOTIFY_INCLUDE_DIFF")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if !include_diff {
+        return summary;
+    }
+
+    let byte_budget = env::var("NOTIFY_DIFF_BYTE_BUDGET")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_DIFF_BYTE_BUDGET);
+
+    let mut diff = String::new();
+    for file in &commit.files {
+        let Some(patch) = &file.patch else { continue };
+        diff.push_str(&format!("--- {}\n{}\n", file.filename, patch));
+        if diff.len() >= byte_budget {
+            break;
+        }
+    }
+    if !diff.is_empty() {
+        let was_truncated = diff.len() > byte_budget;
+        let mut cut = byte_budget.min(diff.len());
+        while cut > 0 && !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        diff.truncate(cut);
+        summary.push_str("\n\n");
+        summary.push_str(&diff);
+        if was_truncated {
+            summary.push_str("\n[diff truncated]");
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CommitAuthor, CommitDetails, CommitFile, CommitStats};
+    use std::sync::Mutex;
+
+    // `format_diff_summary` reads NOTIFY_INCLUDE_DIFF/NOTIFY_DIFF_BYTE_BUDGET from
+    // the process environment, so tests that set them must not run concurrently.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn commit_with_patch(patch: &str) -> FullCommit {
+        FullCommit {
+            html_url: "https://example.invalid/commit/abc".to_string(),
+            commit: CommitDetails {
+                message: "a commit".to_string(),
+                author: CommitAuthor { name: "author".to_string() },
+            },
+            stats: Some(CommitStats { additions: 1, deletions: 1 }),
+            files: vec![CommitFile {
+                filename: "file.rs".to_string(),
+                patch: Some(patch.to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn format_diff_summary_omits_diff_text_without_notify_include_diff() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::remove_var("NOTIFY_INCLUDE_DIFF");
+        env::remove_var("NOTIFY_DIFF_BYTE_BUDGET");
+
+        let summary = format_diff_summary(&commit_with_patch("+line"));
+        assert_eq!(summary, "1 file changed, +1/-1");
+    }
+
+    #[test]
+    fn format_diff_summary_does_not_claim_truncation_when_the_diff_fits() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("NOTIFY_INCLUDE_DIFF", "1");
+        env::set_var("NOTIFY_DIFF_BYTE_BUDGET", "4000");
+
+        let summary = format_diff_summary(&commit_with_patch("+a short line"));
+        env::remove_var("NOTIFY_INCLUDE_DIFF");
+        env::remove_var("NOTIFY_DIFF_BYTE_BUDGET");
+
+        assert!(summary.contains("+a short line"));
+        assert!(!summary.contains("[diff truncated]"));
+    }
+
+    #[test]
+    fn format_diff_summary_marks_truncation_when_the_diff_exceeds_the_budget() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("NOTIFY_INCLUDE_DIFF", "1");
+        env::set_var("NOTIFY_DIFF_BYTE_BUDGET", "10");
+
+        let summary = format_diff_summary(&commit_with_patch(&"+line\n".repeat(10)));
+        env::remove_var("NOTIFY_INCLUDE_DIFF");
+        env::remove_var("NOTIFY_DIFF_BYTE_BUDGET");
+
+        assert!(summary.contains("[diff truncated]"));
+    }
 }
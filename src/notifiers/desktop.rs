@@ -0,0 +1,19 @@
+use super::{Notification, Notifier};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+
+/// Cross-platform desktop popup, replacing the old `notify-send` shell-out which
+/// only worked on Linux.
+pub struct DesktopNotifier;
+
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    async fn send(&self, notification: &Notification<'_>) -> Result<()> {
+        let title = notification.title.to_string();
+        let body = notification.body.to_string();
+        tokio::task::spawn_blocking(move || notifica::notify(&title, &body))
+            .await
+            .context("desktop notification task panicked")?
+            .map_err(|e| anyhow!("Failed to show desktop notification: {}", e))
+    }
+}
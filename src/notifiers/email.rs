@@ -0,0 +1,71 @@
+use super::{Notification, Notifier};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::env;
+
+/// SMTP settings pulled from the environment, kept separate from `EmailNotifier`
+/// so `from_env` can fail fast (missing config) without constructing a transport.
+pub struct EmailConfig {
+    from: Mailbox,
+    to: Mailbox,
+    host: String,
+    username: String,
+    password: String,
+}
+
+impl EmailConfig {
+    /// Returns `None` when email delivery isn't configured, so it's simply left out
+    /// of the sink list rather than erroring the whole process.
+    pub fn from_env() -> Option<Self> {
+        let from = env::var("EMAIL_FROM").ok()?;
+        let to = env::var("EMAIL_TO").ok()?;
+        let host = env::var("SMTP_HOST").ok()?;
+        let username = env::var("SMTP_USERNAME").ok()?;
+        let password = env::var("SMTP_PASSWORD").ok()?;
+
+        Some(Self {
+            from: from.parse().ok()?,
+            to: to.parse().ok()?,
+            host,
+            username,
+            password,
+        })
+    }
+}
+
+pub struct EmailNotifier {
+    config: EmailConfig,
+}
+
+impl EmailNotifier {
+    pub fn new(config: EmailConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn send(&self, notification: &Notification<'_>) -> Result<()> {
+        let message = Message::builder()
+            .from(self.config.from.clone())
+            .to(self.config.to.clone())
+            .subject(notification.title)
+            .body(notification.body.to_string())
+            .context("Failed to build notification email")?;
+
+        let creds = Credentials::new(self.config.username.clone(), self.config.password.clone());
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(&self.config.host)
+            .context("Failed to configure SMTP relay")?
+            .credentials(creds)
+            .build();
+
+        transport
+            .send(message)
+            .await
+            .context("Failed to send notification email")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+mod desktop;
+mod email;
+mod webhook;
+
+pub use desktop::DesktopNotifier;
+pub use email::EmailConfig;
+pub use email::EmailNotifier;
+pub use webhook::WebhookNotifier;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::env;
+
+/// Which `notify_*` method produced a notification, so sinks that care about
+/// structure (e.g. the signed outbound webhook) don't have to parse it back out
+/// of the human-readable title/body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Commit,
+    PullRequest,
+    Branch,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Commit => "commit",
+            EventKind::PullRequest => "pull_request",
+            EventKind::Branch => "branch",
+        }
+    }
+}
+
+/// Everything a sink might need, assembled once in `GithubNotifier::notify_*` and
+/// fanned out to every configured sink.
+pub struct Notification<'a> {
+    pub kind: EventKind,
+    pub repo: &'a str,
+    pub url: &'a str,
+    pub author: &'a str,
+    pub message: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+}
+
+/// A destination notifications are fanned out to. Implementations should not panic
+/// on delivery failure; `send` returns a `Result` so the caller can log and move on
+/// without letting one broken sink take down the others.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, notification: &Notification<'_>) -> Result<()>;
+}
+
+/// Build the list of configured sinks from the environment. At least the desktop
+/// sink is always enabled so behavior is unchanged for users who configure nothing.
+pub fn sinks_from_env() -> Vec<Box<dyn Notifier>> {
+    let mut sinks: Vec<Box<dyn Notifier>> = vec![Box::new(DesktopNotifier)];
+
+    if let Some(email_config) = EmailConfig::from_env() {
+        sinks.push(Box::new(EmailNotifier::new(email_config)));
+    }
+
+    if let Ok(webhook_url) = env::var("NOTIFY_WEBHOOK_URL") {
+        let secrets = env::var("NOTIFY_WEBHOOK_SECRET")
+            .ok()
+            .map(|s| s.split_whitespace().map(String::from).collect())
+            .unwrap_or_default();
+        sinks.push(Box::new(WebhookNotifier::new(webhook_url, secrets)));
+    }
+
+    sinks
+}
@@ -0,0 +1,130 @@
+use super::{Notification, Notifier};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    event: &'a str,
+    repo: &'a str,
+    url: &'a str,
+    author: &'a str,
+    message: &'a str,
+}
+
+/// Outbound webhook delivery signed per the Standard Webhooks spec
+/// (https://www.standardwebhooks.com), so downstream receivers can verify the
+/// payload came from us. `secrets` holds one or more signing secrets, space
+/// separated in config, so a secret can be rotated without a gap where
+/// receivers reject every delivery.
+pub struct WebhookNotifier {
+    url: String,
+    secrets: Vec<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, secrets: Vec<String>) -> Self {
+        Self {
+            url,
+            secrets,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn send(&self, notification: &Notification<'_>) -> Result<()> {
+        let payload = WebhookPayload {
+            event: notification.kind.as_str(),
+            repo: notification.repo,
+            url: notification.url,
+            author: notification.author,
+            message: notification.message,
+        };
+        let body = serde_json::to_string(&payload).context("Failed to serialize webhook payload")?;
+
+        let id = Uuid::new_v4().to_string();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("System clock is before the Unix epoch")?
+            .as_secs();
+
+        let mut request = self.client.post(&self.url).header("webhook-id", &id).header(
+            "webhook-timestamp",
+            timestamp.to_string(),
+        );
+
+        if !self.secrets.is_empty() {
+            let signature = sign(&self.secrets, &id, timestamp, &body)?;
+            request = request.header("webhook-signature", signature);
+        }
+
+        let response = request
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .context("Failed to deliver webhook notification")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Webhook notification rejected with status {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// One `v1,{signature}` token per configured secret, space separated, so a
+/// receiver that knows any one of the secrets can verify the delivery.
+fn sign(secrets: &[String], id: &str, timestamp: u64, body: &str) -> Result<String> {
+    let signed_content = format!("{}.{}.{}", id, timestamp, body);
+
+    let mut tokens = Vec::with_capacity(secrets.len());
+    for secret in secrets {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+            .context("Webhook signing secret has invalid length")?;
+        mac.update(signed_content.as_bytes());
+        let signature = BASE64.encode(mac.finalize().into_bytes());
+        tokens.push(format!("v1,{}", signature));
+    }
+    Ok(tokens.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_is_deterministic_for_the_same_inputs() {
+        let secrets = vec!["whsec_test".to_string()];
+        let a = sign(&secrets, "msg_1", 1700000000, "{\"event\":\"commit\"}").unwrap();
+        let b = sign(&secrets, "msg_1", 1700000000, "{\"event\":\"commit\"}").unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn sign_changes_with_the_body() {
+        let secrets = vec!["whsec_test".to_string()];
+        let a = sign(&secrets, "msg_1", 1700000000, "{\"event\":\"commit\"}").unwrap();
+        let b = sign(&secrets, "msg_1", 1700000000, "{\"event\":\"branch\"}").unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn sign_emits_one_v1_token_per_secret() {
+        let secrets = vec!["whsec_one".to_string(), "whsec_two".to_string()];
+        let signature = sign(&secrets, "msg_1", 1700000000, "{}").unwrap();
+        let tokens: Vec<&str> = signature.split(' ').collect();
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens.iter().all(|t| t.starts_with("v1,")));
+    }
+}
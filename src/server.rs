@@ -0,0 +1,191 @@
+use crate::models::{PullRequestEvent, PushEvent};
+use crate::notifier::GithubNotifier;
+use anyhow::{Context, Result};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::net::SocketAddr;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Clone)]
+struct AppState {
+    notifier: GithubNotifier,
+    webhook_secret: String,
+}
+
+/// Run an HTTP server that receives GitHub webhook deliveries and feeds them into
+/// the same notification paths used by the polling loop, instead of waiting for
+/// the next `check_all_repos` tick.
+pub async fn serve(notifier: GithubNotifier, addr: SocketAddr, webhook_secret: String) -> Result<()> {
+    let state = AppState {
+        notifier,
+        webhook_secret,
+    };
+
+    let app = Router::new()
+        .route("/webhook", post(handle_webhook))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind webhook server on {}", addr))?;
+    println!("Listening for GitHub webhooks on {}", addr);
+    axum::serve(listener, app).await.context("Webhook server stopped")?;
+    Ok(())
+}
+
+async fn handle_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if !verify_signature(&state.webhook_secret, &headers, &body) {
+        eprintln!("Rejected webhook delivery: missing or invalid signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let event = headers
+        .get("X-GitHub-Event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    match event {
+        "push" => handle_push(&state.notifier, &body).await,
+        "pull_request" => handle_pull_request(&state.notifier, &body).await,
+        other => {
+            println!("Ignoring unhandled webhook event: {}", other);
+        }
+    }
+
+    StatusCode::OK
+}
+
+fn verify_signature(secret: &str, headers: &HeaderMap, body: &[u8]) -> bool {
+    let Some(header_value) = headers
+        .get("X-Hub-Signature-256")
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let Some(hex_sig) = header_value.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(sig_bytes) = hex::decode(hex_sig) else {
+        return false;
+    };
+
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&sig_bytes).is_ok()
+}
+
+async fn handle_push(notifier: &GithubNotifier, body: &[u8]) {
+    let event: PushEvent = match serde_json::from_slice(body) {
+        Ok(event) => event,
+        Err(e) => {
+            eprintln!("Failed to parse push event: {}", e);
+            return;
+        }
+    };
+
+    let Some(commit) = event.head_commit else {
+        return;
+    };
+    let branch_name = event
+        .git_ref
+        .strip_prefix("refs/heads/")
+        .unwrap_or(&event.git_ref);
+
+    notifier
+        .notify_commit(
+            &event.repository.full_name,
+            branch_name,
+            &commit.author.name,
+            &commit.message,
+            &commit.url,
+            "",
+        )
+        .await;
+}
+
+async fn handle_pull_request(notifier: &GithubNotifier, body: &[u8]) {
+    let event: PullRequestEvent = match serde_json::from_slice(body) {
+        Ok(event) => event,
+        Err(e) => {
+            eprintln!("Failed to parse pull_request event: {}", e);
+            return;
+        }
+    };
+
+    if event.action != "opened" {
+        return;
+    }
+
+    let pr = &event.pull_request;
+    let author = pr.user.name.as_deref().unwrap_or(&pr.user.login);
+    notifier
+        .notify_pr(&event.repository.full_name, pr.id, &pr.title, author, &pr.html_url)
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with_signature(secret: &str, body: &[u8]) -> HeaderMap {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Hub-Signature-256",
+            format!("sha256={}", signature).parse().unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn accepts_a_correctly_signed_body() {
+        let body = b"{\"ref\":\"refs/heads/main\"}";
+        let headers = headers_with_signature("secret", body);
+        assert!(verify_signature("secret", &headers, body));
+    }
+
+    #[test]
+    fn rejects_a_signature_from_the_wrong_secret() {
+        let body = b"{\"ref\":\"refs/heads/main\"}";
+        let headers = headers_with_signature("wrong-secret", body);
+        assert!(!verify_signature("secret", &headers, body));
+    }
+
+    #[test]
+    fn rejects_a_tampered_body() {
+        let body = b"{\"ref\":\"refs/heads/main\"}";
+        let headers = headers_with_signature("secret", body);
+        assert!(!verify_signature("secret", &headers, b"{\"ref\":\"refs/heads/evil\"}"));
+    }
+
+    #[test]
+    fn rejects_a_missing_signature_header() {
+        let body = b"{\"ref\":\"refs/heads/main\"}";
+        assert!(!verify_signature("secret", &HeaderMap::new(), body));
+    }
+
+    #[test]
+    fn rejects_a_malformed_signature_header() {
+        let body = b"{\"ref\":\"refs/heads/main\"}";
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Hub-Signature-256", "not-hex-or-prefixed".parse().unwrap());
+        assert!(!verify_signature("secret", &headers, body));
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Persists the `seen_*`/`etags` maps across restarts so a restart doesn't either
+/// re-notify everything (lost seen-state) or re-fetch everything (lost ETags).
+pub struct Store {
+    conn: Mutex<Connection>,
+}
+
+impl Store {
+    pub fn open(db_path: &str) -> Result<Self> {
+        let conn = Connection::open(db_path)
+            .with_context(|| format!("Failed to open database at {}", db_path))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS seen_commits (
+                repo_branch TEXT PRIMARY KEY,
+                sha TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS seen_prs (
+                repo TEXT NOT NULL,
+                pr_id INTEGER NOT NULL,
+                PRIMARY KEY (repo, pr_id)
+            );
+            CREATE TABLE IF NOT EXISTS seen_branches (
+                repo TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (repo, name)
+            );
+            CREATE TABLE IF NOT EXISTS etags (
+                url TEXT PRIMARY KEY,
+                etag TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS alert_ledger (
+                hash TEXT PRIMARY KEY,
+                sent_at INTEGER NOT NULL
+            );",
+        )
+        .context("Failed to initialize database schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn load_seen_commits(&self) -> Result<HashMap<String, String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT repo_branch, sha FROM seen_commits")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(rows)
+    }
+
+    pub fn put_seen_commit(&self, repo_branch: &str, sha: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO seen_commits (repo_branch, sha) VALUES (?1, ?2)
+             ON CONFLICT(repo_branch) DO UPDATE SET sha = excluded.sha",
+            (repo_branch, sha),
+        )?;
+        Ok(())
+    }
+
+    pub fn load_seen_prs(&self) -> Result<HashMap<String, HashSet<u64>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT repo, pr_id FROM seen_prs")?;
+        let mut map: HashMap<String, HashSet<u64>> = HashMap::new();
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        for row in rows {
+            let (repo, pr_id) = row?;
+            map.entry(repo).or_default().insert(pr_id);
+        }
+        Ok(map)
+    }
+
+    pub fn put_seen_pr(&self, repo: &str, pr_id: u64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO seen_prs (repo, pr_id) VALUES (?1, ?2)",
+            (repo, pr_id as i64),
+        )?;
+        Ok(())
+    }
+
+    pub fn load_seen_branches(&self) -> Result<HashMap<String, HashSet<String>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT repo, name FROM seen_branches")?;
+        let mut map: HashMap<String, HashSet<String>> = HashMap::new();
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (repo, name) = row?;
+            map.entry(repo).or_default().insert(name);
+        }
+        Ok(map)
+    }
+
+    pub fn put_seen_branch(&self, repo: &str, name: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO seen_branches (repo, name) VALUES (?1, ?2)",
+            (repo, name),
+        )?;
+        Ok(())
+    }
+
+    pub fn load_etags(&self) -> Result<HashMap<String, String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT url, etag FROM etags")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(rows)
+    }
+
+    pub fn put_etag(&self, url: &str, etag: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO etags (url, etag) VALUES (?1, ?2)
+             ON CONFLICT(url) DO UPDATE SET etag = excluded.etag",
+            (url, etag),
+        )?;
+        Ok(())
+    }
+
+    /// `true` if `hash` was last alerted on less than `window_secs` ago, i.e. this
+    /// delivery should be suppressed as a duplicate.
+    pub fn was_recently_alerted(&self, hash: &str, window_secs: u64, now: u64) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let sent_at: Option<i64> = conn
+            .query_row(
+                "SELECT sent_at FROM alert_ledger WHERE hash = ?1",
+                [hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(match sent_at {
+            Some(sent_at) => now.saturating_sub(sent_at as u64) < window_secs,
+            None => false,
+        })
+    }
+
+    pub fn record_alert(&self, hash: &str, now: u64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO alert_ledger (hash, sent_at) VALUES (?1, ?2)
+             ON CONFLICT(hash) DO UPDATE SET sent_at = excluded.sent_at",
+            (hash, now as i64),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_in_memory() -> Store {
+        Store::open(":memory:").unwrap()
+    }
+
+    #[test]
+    fn seen_commit_round_trips_through_the_store() {
+        let store = open_in_memory();
+        store.put_seen_commit("owner/repo#main", "abc123").unwrap();
+        store.put_seen_commit("owner/repo#dev", "def456").unwrap();
+
+        let loaded = store.load_seen_commits().unwrap();
+        assert_eq!(loaded.get("owner/repo#main"), Some(&"abc123".to_string()));
+        assert_eq!(loaded.get("owner/repo#dev"), Some(&"def456".to_string()));
+    }
+
+    #[test]
+    fn put_seen_commit_overwrites_the_previous_sha_for_the_same_branch() {
+        let store = open_in_memory();
+        store.put_seen_commit("owner/repo#main", "abc123").unwrap();
+        store.put_seen_commit("owner/repo#main", "def456").unwrap();
+
+        let loaded = store.load_seen_commits().unwrap();
+        assert_eq!(loaded.get("owner/repo#main"), Some(&"def456".to_string()));
+        assert_eq!(loaded.len(), 1);
+    }
+
+    #[test]
+    fn was_recently_alerted_is_false_before_any_alert_is_recorded() {
+        let store = open_in_memory();
+        assert!(!store.was_recently_alerted("some-hash", 300, 1_000).unwrap());
+    }
+
+    #[test]
+    fn was_recently_alerted_is_true_inside_the_dedupe_window() {
+        let store = open_in_memory();
+        store.record_alert("some-hash", 1_000).unwrap();
+        assert!(store.was_recently_alerted("some-hash", 300, 1_299).unwrap());
+    }
+
+    #[test]
+    fn was_recently_alerted_is_false_once_the_dedupe_window_has_elapsed() {
+        let store = open_in_memory();
+        store.record_alert("some-hash", 1_000).unwrap();
+        assert!(!store.was_recently_alerted("some-hash", 300, 1_300).unwrap());
+    }
+}